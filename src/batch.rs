@@ -0,0 +1,73 @@
+/*
+ * Copyright (C) 2021-2025 K4YT3X.
+ *
+ * This program is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU General Public License
+ * as published by the Free Software Foundation; only version 2
+ * of the License.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+//! a tiny length/size-bounded batch accumulator, used to keep a single
+//! outgoing request (e.g. a Telegram media group) from growing unbounded
+//! in memory
+
+/// default number of items a [`Batcher`] accumulates before it is full
+pub const DEFAULT_BATCH_MAX_ITEMS: usize = 20;
+
+/// default accumulated byte size a [`Batcher`] allows before it is full
+pub const DEFAULT_BATCH_MAX_BYTES: usize = 5 * 1024_usize.pow(2);
+
+/// accumulates items up to a max count or a max total byte size,
+/// whichever is hit first
+pub struct Batcher<T> {
+    max_items: usize,
+    max_bytes: usize,
+    items: Vec<T>,
+    bytes: usize,
+}
+
+impl<T> Batcher<T> {
+    pub fn new(max_items: usize, max_bytes: usize) -> Batcher<T> {
+        Batcher {
+            max_items,
+            max_bytes,
+            items: Vec::new(),
+            bytes: 0,
+        }
+    }
+
+    /// try to add `item`, which is `item_bytes` bytes large
+    ///
+    /// returns `false` without adding it (dropping `item` instead) if the
+    /// batch is already non-empty and adding it would exceed either
+    /// threshold
+    pub fn try_push(&mut self, item: T, item_bytes: usize) -> bool {
+        if !self.items.is_empty()
+            && (self.items.len() >= self.max_items || self.bytes + item_bytes > self.max_bytes)
+        {
+            return false;
+        }
+
+        self.items.push(item);
+        self.bytes += item_bytes;
+        true
+    }
+
+    /// number of items currently accumulated
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// consume and return the accumulated items, resetting the batcher
+    pub fn take(&mut self) -> Vec<T> {
+        self.bytes = 0;
+        std::mem::take(&mut self.items)
+    }
+}
@@ -0,0 +1,106 @@
+/*
+ * Copyright (C) 2021-2025 K4YT3X.
+ *
+ * This program is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU General Public License
+ * as published by the Free Software Foundation; only version 2
+ * of the License.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+//! a small TTL'd key-value cache used to avoid reposting illustrations
+//! that are still ranked on a later run
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use tokio::task;
+
+/// default time an `illust_id` is remembered before it can be reposted
+pub const DEFAULT_CACHE_TTL: i64 = 45 * 24 * 60 * 60;
+
+/// a key-value store used to remember which illustrations were already
+/// posted, so that a future backend (Redis, Cloudflare KV, ...) can be
+/// dropped in without touching the call sites
+#[async_trait]
+pub trait KvStore: Send + Sync {
+    /// fetch the unix timestamp a key was last written at, if present
+    async fn get(&self, key: &str) -> Result<Option<i64>>;
+
+    /// write a key with the given unix timestamp
+    async fn set(&self, key: &str, timestamp: i64) -> Result<()>;
+}
+
+/// a [`KvStore`] backed by a single JSON file on disk
+pub struct FileKvStore {
+    path: PathBuf,
+    entries: Mutex<HashMap<String, i64>>,
+}
+
+impl FileKvStore {
+    /// open (or create) a JSON-backed store at `path`
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - path to the store's JSON file
+    ///
+    /// # Errors
+    ///
+    /// I/O or deserialization errors
+    pub fn open(path: &Path) -> Result<FileKvStore> {
+        let entries = if path.exists() {
+            serde_json::from_str(&fs::read_to_string(path)?)?
+        }
+        else {
+            HashMap::new()
+        };
+
+        Ok(FileKvStore {
+            path: path.to_owned(),
+            entries: Mutex::new(entries),
+        })
+    }
+
+    /// write `entries` to `path`
+    fn write_to_disk(path: &Path, entries: &HashMap<String, i64>) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string(entries)?)?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl KvStore for FileKvStore {
+    async fn get(&self, key: &str) -> Result<Option<i64>> {
+        Ok(self.entries.lock().unwrap().get(key).copied())
+    }
+
+    async fn set(&self, key: &str, timestamp: i64) -> Result<()> {
+        // snapshot under the lock, then rewrite the file on a blocking
+        // thread so a large cache's full-file rewrite can't stall a
+        // tokio worker that `set` is concurrently called from
+        let snapshot = {
+            let mut entries = self.entries.lock().unwrap();
+            entries.insert(key.to_owned(), timestamp);
+            entries.clone()
+        };
+
+        let path = self.path.clone();
+        task::spawn_blocking(move || FileKvStore::write_to_disk(&path, &snapshot)).await??;
+        Ok(())
+    }
+}
@@ -0,0 +1,88 @@
+/*
+ * Copyright (C) 2021-2025 K4YT3X.
+ *
+ * This program is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU General Public License
+ * as published by the Free Software Foundation; only version 2
+ * of the License.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+//! Fluent-backed caption localization, falling back to `en-US`
+
+use anyhow::{anyhow, Result};
+use fluent::{FluentBundle, FluentResource};
+use unic_langid::LanguageIdentifier;
+
+const EN_US_FTL: &'static str = include_str!("locales/en-US.ftl");
+const JA_FTL: &'static str = include_str!("locales/ja.ftl");
+const ZH_FTL: &'static str = include_str!("locales/zh.ftl");
+
+/// default locale used when no `--lang` is given, or an unknown one is
+pub const DEFAULT_LANG: &'static str = "en-US";
+
+/// a loaded locale's caption labels, with an `en-US` fallback for
+/// missing keys or an unsupported locale
+pub struct Catalog {
+    primary: FluentBundle<FluentResource>,
+    fallback: FluentBundle<FluentResource>,
+}
+
+impl Catalog {
+    /// load the caption labels for `lang`, falling back to `en-US` if
+    /// `lang` is not one of the bundled locales
+    ///
+    /// # Errors
+    ///
+    /// returned if a bundled `.ftl` resource fails to parse, which
+    /// indicates a bug in this crate rather than user input
+    pub fn load(lang: &str) -> Result<Catalog> {
+        let (locale, source) = match lang {
+            "ja" => ("ja", JA_FTL),
+            "zh" => ("zh", ZH_FTL),
+            _ => (DEFAULT_LANG, EN_US_FTL),
+        };
+
+        Ok(Catalog {
+            primary: Self::build_bundle(locale, source)?,
+            fallback: Self::build_bundle(DEFAULT_LANG, EN_US_FTL)?,
+        })
+    }
+
+    fn build_bundle(locale: &str, source: &str) -> Result<FluentBundle<FluentResource>> {
+        let langid: LanguageIdentifier = locale
+            .parse()
+            .map_err(|error| anyhow!("invalid locale {}: {:?}", locale, error))?;
+        let resource = FluentResource::try_new(source.to_owned()).map_err(|(_, errors)| {
+            anyhow!("invalid Fluent resource for {}: {:?}", locale, errors)
+        })?;
+
+        let mut bundle = FluentBundle::new(vec![langid]);
+        bundle.add_resource(resource).map_err(|errors| {
+            anyhow!("failed building Fluent bundle for {}: {:?}", locale, errors)
+        })?;
+
+        Ok(bundle)
+    }
+
+    /// fetch a label, trying the requested locale, then `en-US`, then
+    /// finally falling back to the bare key itself
+    pub fn get(&self, key: &str) -> String {
+        Self::format(&self.primary, key)
+            .or_else(|| Self::format(&self.fallback, key))
+            .unwrap_or_else(|| key.to_owned())
+    }
+
+    fn format(bundle: &FluentBundle<FluentResource>, key: &str) -> Option<String> {
+        let message = bundle.get_message(key)?;
+        let pattern = message.value()?;
+        let mut errors = vec![];
+        Some(bundle.format_pattern(pattern, None, &mut errors).into_owned())
+    }
+}
@@ -0,0 +1,95 @@
+/*
+ * Copyright (C) 2021-2025 K4YT3X.
+ *
+ * This program is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU General Public License
+ * as published by the Free Software Foundation; only version 2
+ * of the License.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+//! hosts for uploading full-resolution artwork that is too large for
+//! Telegram, so it does not have to be destructively downscaled
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use reqwest::header;
+use serde::Deserialize;
+
+const IMGUR_UPLOAD_ENDPOINT: &'static str = "https://api.imgur.com/3/image";
+
+/// a host that full-resolution images can be uploaded to
+#[async_trait]
+pub trait ImageHost: Send + Sync {
+    /// upload `image_bytes` and return a URL the original can be viewed at
+    async fn upload(&self, image_bytes: &[u8]) -> Result<String>;
+}
+
+#[derive(Debug, Deserialize)]
+struct ImgurResponse {
+    success: bool,
+    data: ImgurData,
+}
+
+#[derive(Debug, Deserialize)]
+struct ImgurData {
+    link: Option<String>,
+    error: Option<String>,
+}
+
+/// an [`ImageHost`] backed by Imgur's anonymous image upload API
+pub struct Imgur {
+    client_id: String,
+    client: reqwest::Client,
+}
+
+impl Imgur {
+    pub fn new(client_id: String) -> Imgur {
+        Imgur {
+            client_id,
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl ImageHost for Imgur {
+    async fn upload(&self, image_bytes: &[u8]) -> Result<String> {
+        let response = self
+            .client
+            .post(IMGUR_UPLOAD_ENDPOINT)
+            .header(
+                header::AUTHORIZATION,
+                format!("Client-ID {}", self.client_id),
+            )
+            .multipart(reqwest::multipart::Form::new().part(
+                "image",
+                reqwest::multipart::Part::bytes(image_bytes.to_vec()),
+            ))
+            .send()
+            .await?
+            .json::<ImgurResponse>()
+            .await?;
+
+        if !response.success {
+            return Err(anyhow!(
+                "Imgur upload failed: {}",
+                response
+                    .data
+                    .error
+                    .unwrap_or_else(|| "unknown error".to_owned())
+            ));
+        }
+
+        response
+            .data
+            .link
+            .ok_or_else(|| anyhow!("Imgur upload returned no link"))
+    }
+}
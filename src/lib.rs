@@ -14,7 +14,11 @@
  * You should have received a copy of the GNU General Public License
  * along with this program. If not, see <https://www.gnu.org/licenses/>.
  */
-use std::io::{Cursor, Read, Seek, SeekFrom};
+use std::{
+    io::{Cursor, Read, Seek, SeekFrom},
+    path::PathBuf,
+    sync::Arc,
+};
 
 use anyhow::{anyhow, Result};
 use chrono::Utc;
@@ -29,9 +33,21 @@ use teloxide::{
     types::{ChatId, InputFile, InputMedia, InputMediaPhoto, ParseMode},
     RequestError,
 };
-use tokio::{task, task::JoinHandle};
+use tokio::{sync::Semaphore, task, task::JoinHandle};
 use tracing::{debug, error, info, warn};
 
+mod batch;
+mod cache;
+mod i18n;
+mod imagehost;
+mod mastodon;
+mod telegraph;
+use batch::{Batcher, DEFAULT_BATCH_MAX_BYTES, DEFAULT_BATCH_MAX_ITEMS};
+use cache::{FileKvStore, KvStore};
+use i18n::Catalog;
+use imagehost::{ImageHost, Imgur};
+use telegraph::NodeElement;
+
 pub const VERSION: &'static str = env!("CARGO_PKG_VERSION");
 const MAX_IMAGE_SIZE: usize = 10 * 1024_usize.pow(2);
 const USER_AGENT: &'static str = "PixivAndroidApp/6.135.1 (Android 15; Pixel 9)";
@@ -124,21 +140,145 @@ pub struct Config {
     chat_id: ChatId,
     pages: u32,
     r18: bool,
+    telegraph_token: Option<String>,
+    cache_path: PathBuf,
+    cache_ttl: i64,
+    concurrency: usize,
+    batch_max_items: usize,
+    batch_max_bytes: usize,
+    proxy: Option<String>,
+    mastodon_url: Option<String>,
+    mastodon_token: Option<String>,
+    imgur_client_id: Option<String>,
+    lang: String,
 }
 
 impl Config {
-    pub fn new(token: String, chat_id: i64, pages: u32, r18: bool) -> Config {
+    pub fn new(token: String, chat_id: i64) -> Config {
         Config {
             token,
             chat_id: ChatId(chat_id),
-            pages,
-            r18,
+            pages: 1,
+            r18: false,
+            telegraph_token: None,
+            cache_path: PathBuf::from("pixivdaily_cache.json"),
+            cache_ttl: cache::DEFAULT_CACHE_TTL,
+            concurrency: 20,
+            batch_max_items: DEFAULT_BATCH_MAX_ITEMS,
+            batch_max_bytes: DEFAULT_BATCH_MAX_BYTES,
+            proxy: None,
+            mastodon_url: None,
+            mastodon_token: None,
+            imgur_client_id: None,
+            lang: i18n::DEFAULT_LANG.to_owned(),
         }
     }
+
+    /// set the number of Pixiv daily ranking pages to fetch
+    pub fn with_pages(mut self, pages: u32) -> Config {
+        self.pages = pages;
+        self
+    }
+
+    /// set whether the R18 ranking is fetched instead of the regular one
+    pub fn with_r18(mut self, r18: bool) -> Config {
+        self.r18 = r18;
+        self
+    }
+
+    /// enable the Telegraph overflow path for manga with more than 10
+    /// pages by providing a Telegraph account access token
+    pub fn with_telegraph_token(mut self, telegraph_token: Option<String>) -> Config {
+        self.telegraph_token = telegraph_token;
+        self
+    }
+
+    /// set the path of the dedup cache's backing file
+    pub fn with_cache_path(mut self, cache_path: PathBuf) -> Config {
+        self.cache_path = cache_path;
+        self
+    }
+
+    /// set how long (in seconds) a posted `illust_id` is remembered for
+    pub fn with_cache_ttl(mut self, cache_ttl: i64) -> Config {
+        self.cache_ttl = cache_ttl;
+        self
+    }
+
+    /// set the maximum number of detail-fetch/send tasks allowed to run
+    /// at once
+    pub fn with_concurrency(mut self, concurrency: usize) -> Config {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// set the maximum number of pages that go into a single Telegram
+    /// media group (still hard-capped at 10 by the Bot API)
+    pub fn with_batch_max_items(mut self, batch_max_items: usize) -> Config {
+        self.batch_max_items = batch_max_items;
+        self
+    }
+
+    /// set the maximum accumulated byte size of a single Telegram media
+    /// group's pages
+    pub fn with_batch_max_bytes(mut self, batch_max_bytes: usize) -> Config {
+        self.batch_max_bytes = batch_max_bytes;
+        self
+    }
+
+    /// route all outgoing Pixiv requests through a SOCKS/HTTP proxy
+    pub fn with_proxy(mut self, proxy: Option<String>) -> Config {
+        self.proxy = proxy;
+        self
+    }
+
+    /// enable cross-posting each illustration to a Mastodon instance
+    pub fn with_mastodon(
+        mut self,
+        mastodon_url: Option<String>,
+        mastodon_token: Option<String>,
+    ) -> Config {
+        self.mastodon_url = mastodon_url;
+        self.mastodon_token = mastodon_token;
+        self
+    }
+
+    /// upload oversized artwork to Imgur instead of downscaling it, and
+    /// link the full-resolution original in the caption
+    pub fn with_imgur_client_id(mut self, imgur_client_id: Option<String>) -> Config {
+        self.imgur_client_id = imgur_client_id;
+        self
+    }
+
+    /// set the locale captions are rendered in, falling back to `en-US`
+    /// if it is not one of the bundled locales
+    pub fn with_lang(mut self, lang: String) -> Config {
+        self.lang = lang;
+        self
+    }
+}
+
+/// build the `reqwest::Client` used for all Pixiv requests, routed
+/// through `config`'s proxy if one is set
+///
+/// # Errors
+///
+/// reqwest errors
+fn build_pixiv_client(config: &Config) -> Result<reqwest::Client, reqwest::Error> {
+    let mut builder = reqwest::Client::builder().user_agent(USER_AGENT);
+    if let Some(proxy) = &config.proxy {
+        builder = builder.proxy(reqwest::Proxy::all(proxy)?);
+    }
+    builder.build()
 }
 
 /// retrieve and deserialize pixiv daily rankings
 ///
+/// # Arguments
+///
+/// * `config` - an instance of Config
+/// * `client` - the `reqwest::Client` to issue requests with
+///
 /// # Errors
 ///
 /// reqwest errors
@@ -146,10 +286,12 @@ impl Config {
 /// # Examples
 ///
 /// ```
-/// let ranking = get_pixiv_daily_ranking(&config).await?;
+/// let ranking = get_pixiv_daily_ranking(&config, &client).await?;
 /// ```
-async fn get_pixiv_daily_ranking(config: &Config) -> Result<Vec<RankingIllust>, reqwest::Error> {
-    let client = reqwest::Client::builder().user_agent(USER_AGENT).build()?;
+async fn get_pixiv_daily_ranking(
+    config: &Config,
+    client: &reqwest::Client,
+) -> Result<Vec<RankingIllust>, reqwest::Error> {
     let mut illusts = Vec::new();
 
     for page in 1..config.pages + 1 {
@@ -184,6 +326,7 @@ async fn get_pixiv_daily_ranking(config: &Config) -> Result<Vec<RankingIllust>,
 ///
 /// # Arguments
 ///
+/// * `client` - the `reqwest::Client` to issue requests with
 /// * `id` - illust ID
 ///
 /// # Errors
@@ -193,10 +336,12 @@ async fn get_pixiv_daily_ranking(config: &Config) -> Result<Vec<RankingIllust>,
 /// # Examples
 ///
 /// ```
-/// let illust_details = get_illust_details("87469406").await?;
+/// let illust_details = get_illust_details(&client, "87469406").await?;
 /// ```
-async fn get_illust_details(id: String) -> Result<Illust, reqwest::Error> {
-    let client = reqwest::Client::builder().user_agent(USER_AGENT).build()?;
+async fn get_illust_details(
+    client: &reqwest::Client,
+    id: String,
+) -> Result<Illust, reqwest::Error> {
     let illust_response = client
         .get(format!(
             "https://www.pixiv.net/touch/ajax/illust/details?illust_id={}",
@@ -214,6 +359,7 @@ async fn get_illust_details(id: String) -> Result<Illust, reqwest::Error> {
 ///
 /// # Arguments
 ///
+/// * `client` - the `reqwest::Client` to issue requests with
 /// * `url` - URL of the image
 /// * `referer` - Referer header to set
 ///
@@ -224,11 +370,14 @@ async fn get_illust_details(id: String) -> Result<Illust, reqwest::Error> {
 /// # Examples
 ///
 /// ```
-/// let image_bytes = download_image(&"https://example.com/example.png",
+/// let image_bytes = download_image(&client, &"https://example.com/example.png",
 /// &"https://example.com").await?
 /// ```
-async fn download_image(url: &String, referer: &String) -> Result<Vec<u8>, reqwest::Error> {
-    let client = reqwest::Client::builder().user_agent(USER_AGENT).build()?;
+async fn download_image(
+    client: &reqwest::Client,
+    url: &String,
+    referer: &String,
+) -> Result<Vec<u8>, reqwest::Error> {
     Ok(client
         .get(url)
         .header(header::REFERER, referer)
@@ -359,49 +508,153 @@ fn markdown_escape(text: &String) -> String {
         .replace("!", "\\!")
 }
 
-/// send an illustration to the Telegram chat
+/// an illustration's caption, assembled once and rendered per sink
+///
+/// Telegram gets a MarkdownV2 rendering with links; other sinks (e.g.
+/// Mastodon) get a plain-text rendering, so escaping stays Telegram-only
+struct Caption {
+    illust_id: String,
+    title: String,
+    author_name: String,
+    author_id: String,
+    tags: Vec<String>,
+    pages: Option<usize>,
+    gallery_url: Option<String>,
+    original_url: Option<String>,
+}
+
+impl Caption {
+    /// render the caption as Telegram MarkdownV2, with links; labels come
+    /// from `catalog` and escaping is applied after substitution
+    fn to_markdown_v2(&self, catalog: &Catalog) -> String {
+        let tag_strings: Vec<String> = self
+            .tags
+            .iter()
+            .map(|tag| {
+                format!(
+                    "[\\#{}](https://www\\.pixiv\\.net/tags/{}/artworks)",
+                    markdown_escape(tag),
+                    markdown_escape(tag)
+                )
+            })
+            .collect();
+
+        let mut lines = vec![
+            format!(
+                "{}: [{} \\({}\\)](https://www\\.pixiv\\.net/artworks/{})",
+                markdown_escape(&catalog.get("caption-title")),
+                markdown_escape(&self.title),
+                self.illust_id,
+                self.illust_id
+            ),
+            format!(
+                "{}: [{}](https://www\\.pixiv\\.net/users/{})",
+                markdown_escape(&catalog.get("caption-author")),
+                markdown_escape(&self.author_name),
+                self.author_id
+            ),
+            format!(
+                "{}: {}",
+                markdown_escape(&catalog.get("caption-tags")),
+                tag_strings.join(", ")
+            ),
+        ];
+
+        if let Some(pages) = self.pages {
+            lines.push(format!(
+                "{}: {}",
+                markdown_escape(&catalog.get("caption-pages")),
+                pages
+            ));
+        }
+        if let Some(url) = &self.gallery_url {
+            lines.push(format!("Full gallery: {}", markdown_escape(url)));
+        }
+        if let Some(url) = &self.original_url {
+            lines.push(format!("View original: {}", markdown_escape(url)));
+        }
+
+        lines.join("\n")
+    }
+
+    /// render the caption as unescaped plain text, for non-Telegram sinks;
+    /// labels come from `catalog`
+    fn to_plain_text(&self, catalog: &Catalog) -> String {
+        let tag_strings: Vec<String> =
+            self.tags.iter().map(|tag| format!("#{}", tag)).collect();
+
+        let mut lines = vec![
+            format!(
+                "{}: {} (https://www.pixiv.net/artworks/{})",
+                catalog.get("caption-title"),
+                self.title,
+                self.illust_id
+            ),
+            format!(
+                "{}: {} (https://www.pixiv.net/users/{})",
+                catalog.get("caption-author"),
+                self.author_name,
+                self.author_id
+            ),
+            format!("{}: {}", catalog.get("caption-tags"), tag_strings.join(", ")),
+        ];
+
+        if let Some(pages) = self.pages {
+            lines.push(format!("{}: {}", catalog.get("caption-pages"), pages));
+        }
+        if let Some(url) = &self.gallery_url {
+            lines.push(format!("Full gallery: {}", url));
+        }
+        if let Some(url) = &self.original_url {
+            lines.push(format!("View original: {}", url));
+        }
+
+        lines.join("\n")
+    }
+}
+
+/// send an illustration to the Telegram chat, and optionally cross-post
+/// it to Mastodon
 ///
 /// # Arguments
 ///
 /// * `config` - an instance of Config
 /// * `bot` - an instance of Throttle<Bot>
+/// * `cache` - dedup cache used to record that this illustration was sent
+/// * `client` - the `reqwest::Client` used to download the illustration
+/// * `telegraph_client` - the `reqwest::Client` used for Telegraph requests
+/// * `catalog` - caption label localization, loaded from `config.lang`
 /// * `illust` - an Illust struct which represents an illustration
 /// * `send_sleep` - global sleep timer
 ///
 /// # Errors
 ///
 /// any error that implements the Error trait
-async fn send_illust<'a>(config: Config, bot: Throttle<Bot>, illust: Illust) -> Result<()> {
-    let mut tag_strings = vec![];
-    for tag in &illust.tags {
-        tag_strings.push(
-            format!(
-                "[\\#{}](https://www\\.pixiv\\.net/tags/{}/artworks)",
-                markdown_escape(tag),
-                markdown_escape(tag)
-            )
-            .to_owned(),
-        );
-    }
-
-    // format captions
-    // each element is one line
-    let mut captions = vec![
-        format!(
-            "Title: [{} \\({}\\)](https://www\\.pixiv\\.net/artworks/{})",
-            markdown_escape(&illust.title),
-            illust.id,
-            illust.id
-        )
-        .to_owned(),
-        format!(
-            "Author: [{}](https://www\\.pixiv\\.net/users/{})",
-            markdown_escape(&illust.author_details.user_name),
-            illust.author_details.user_id
-        )
-        .to_owned(),
-        format!("Tags: {}", tag_strings.join(", ")),
-    ];
+async fn send_illust<'a>(
+    config: Config,
+    bot: Throttle<Bot>,
+    cache: Arc<dyn KvStore>,
+    client: reqwest::Client,
+    telegraph_client: reqwest::Client,
+    catalog: Arc<Catalog>,
+    illust: Illust,
+) -> Result<()> {
+    let mut caption = Caption {
+        illust_id: illust.id.clone(),
+        title: illust.title.clone(),
+        author_name: illust.author_details.user_name.clone(),
+        author_id: illust.author_details.user_id.clone(),
+        tags: illust.tags.clone(),
+        pages: None,
+        gallery_url: None,
+        original_url: None,
+    };
+
+    // bytes of the first image sent, reused as the Mastodon attachment
+    let mut mastodon_image_bytes: Option<Vec<u8>> = None;
+
+    // upload oversized originals here instead of only downscaling them
+    let imgur = config.imgur_client_id.clone().map(Imgur::new);
 
     // holds all InputMedia enums for sendMediaGroup
     let mut images = Vec::new();
@@ -409,16 +662,50 @@ async fn send_illust<'a>(config: Config, bot: Throttle<Bot>, illust: Illust) ->
     // if illustration is a manga
     if let (Some(manga), Some(illust_images)) = (illust.manga_a, illust.illust_images) {
         // update the caption with the manga's page count
-        captions.push(format!("Pages: {}", manga.len()));
+        caption.pages = Some(manga.len());
+
+        // a media group can hold at most 10 photos; manga with more pages
+        // than that get the rest published to Telegraph instead of dropped
+        let overflows = manga.len() > 10;
+        let mut telegraph_nodes = Vec::new();
+
+        // Telegram allows at most 10 photos per media group; the byte
+        // threshold additionally keeps a batch of full-resolution pages
+        // from growing unbounded in memory
+        let batch_cap = config.batch_max_items.min(10);
+        let mut image_batch: Batcher<InputMedia> = Batcher::new(batch_cap, config.batch_max_bytes);
 
         // add each manga into images
         for image in manga {
+            // without a Telegraph token there is nowhere to publish pages
+            // beyond the media group's cap, so stop downloading once it's
+            // full instead of paying for every page's fetch and resize
+            if overflows && config.telegraph_token.is_none() && image_batch.len() >= batch_cap {
+                break;
+            }
+
             info!(
                 id = %illust.id,
                 page = image.page,
                 "Retrieving manga"
             );
-            let original_image = download_image(&image.url, &illust.meta.canonical).await?;
+            let original_image = download_image(&client, &image.url, &illust.meta.canonical).await?;
+
+            // preserve full quality by linking an oversized original
+            // instead of only ever downscaling it
+            if caption.original_url.is_none() && original_image.len() > MAX_IMAGE_SIZE {
+                if let Some(imgur) = &imgur {
+                    match imgur.upload(&original_image).await {
+                        Ok(url) => caption.original_url = Some(url),
+                        Err(error) => warn!(
+                            id = %illust.id,
+                            error = ?error,
+                            "Failed uploading oversized page to Imgur"
+                        ),
+                    }
+                }
+            }
+
             let image_bytes = resize_image(
                 original_image,
                 &illust.id,
@@ -430,24 +717,84 @@ async fn send_illust<'a>(config: Config, bot: Throttle<Bot>, illust: Illust) ->
                     .parse::<u32>()?,
             )
             .await?;
-            images.push(InputMedia::Photo(InputMediaPhoto {
-                media: InputFile::memory(image_bytes),
-                caption: match images.len() {
-                    0 => Some(captions.join("\n")),
-                    _ => None,
-                },
-                parse_mode: match images.len() {
-                    0 => Some(ParseMode::MarkdownV2),
-                    _ => None,
-                },
-                caption_entities: None,
-                has_spoiler: false,
-            }));
-
-            // one media group can contain a max of 10 images
-            if images.len() == 10 {
-                break;
+
+            // upload every page to Telegraph so the full set can be linked;
+            // a flaky upload here shouldn't take down the primary Telegram
+            // send, so log and simply omit this page from the Telegraph page
+            if overflows && config.telegraph_token.is_some() {
+                match telegraph::upload_media(&telegraph_client, image_bytes.clone()).await {
+                    Ok(src) => telegraph_nodes.push(NodeElement::img(src)),
+                    Err(error) => warn!(
+                        id = %illust.id,
+                        page = image.page,
+                        error = ?error,
+                        "Failed uploading manga page to Telegraph"
+                    ),
+                }
+            }
+
+            // remember the first page's bytes for the Mastodon attachment
+            if mastodon_image_bytes.is_none() {
+                mastodon_image_bytes = Some(image_bytes.clone());
             }
+
+            // one media group can contain a max of 10 images, and the
+            // batch is additionally capped in total byte size; pages that
+            // do not fit are not sent to Telegram, and appear only in the
+            // Telegraph page above, which is built only for manga with more
+            // than 10 pages when a Telegraph token is set
+            let image_len = image_bytes.len();
+            image_batch.try_push(
+                InputMedia::Photo(InputMediaPhoto {
+                    media: InputFile::memory(image_bytes),
+                    caption: None,
+                    parse_mode: None,
+                    caption_entities: None,
+                    has_spoiler: false,
+                }),
+                image_len,
+            );
+        }
+
+        images.append(&mut image_batch.take());
+
+        // publish the full manga to Telegraph and link it in the caption
+        if overflows {
+            match &config.telegraph_token {
+                Some(token) => {
+                    info!(
+                        id = %illust.id,
+                        pages = telegraph_nodes.len(),
+                        "Publishing full manga to Telegraph"
+                    );
+                    match telegraph::create_page(
+                        &telegraph_client,
+                        token,
+                        &illust.title,
+                        &illust.author_details.user_name,
+                        telegraph_nodes,
+                    )
+                    .await
+                    {
+                        Ok(url) => caption.gallery_url = Some(url),
+                        Err(error) => warn!(
+                            id = %illust.id,
+                            error = ?error,
+                            "Failed publishing Telegraph page"
+                        ),
+                    }
+                }
+                None => debug!(
+                    id = %illust.id,
+                    "Manga exceeds 10 pages and no Telegraph access token is configured"
+                ),
+            }
+        }
+
+        // attach the (now final) caption to the first image in the group
+        if let Some(InputMedia::Photo(first)) = images.first_mut() {
+            first.caption = Some(caption.to_markdown_v2(&catalog));
+            first.parse_mode = Some(ParseMode::MarkdownV2);
         }
     }
     // if this is not a manga
@@ -462,7 +809,23 @@ async fn send_illust<'a>(config: Config, bot: Throttle<Bot>, illust: Illust) ->
             }
         };
 
-        let original_image = download_image(&url, &illust.meta.canonical).await?;
+        let original_image = download_image(&client, &url, &illust.meta.canonical).await?;
+
+        // preserve full quality by linking an oversized original instead
+        // of only ever downscaling it
+        if original_image.len() > MAX_IMAGE_SIZE {
+            if let Some(imgur) = &imgur {
+                match imgur.upload(&original_image).await {
+                    Ok(url) => caption.original_url = Some(url),
+                    Err(error) => warn!(
+                        id = %illust.id,
+                        error = ?error,
+                        "Failed uploading oversized artwork to Imgur"
+                    ),
+                }
+            }
+        }
+
         let image_bytes = resize_image(
             original_image,
             &illust.id,
@@ -470,9 +833,10 @@ async fn send_illust<'a>(config: Config, bot: Throttle<Bot>, illust: Illust) ->
             illust.height.parse::<u32>()?,
         )
         .await?;
+        mastodon_image_bytes = Some(image_bytes.clone());
         images.push(InputMedia::Photo(InputMediaPhoto {
             media: InputFile::memory(image_bytes),
-            caption: Some(captions.join("\n")),
+            caption: Some(caption.to_markdown_v2(&catalog)),
             parse_mode: Some(ParseMode::MarkdownV2),
             caption_entities: None,
             has_spoiler: false,
@@ -521,6 +885,30 @@ async fn send_illust<'a>(config: Config, bot: Throttle<Bot>, illust: Illust) ->
         Err(error.into())
     }
     else {
+        // cross-post to Mastodon, if configured
+        if let (Some(mastodon_url), Some(mastodon_token), Some(image_bytes)) = (
+            &config.mastodon_url,
+            &config.mastodon_token,
+            &mastodon_image_bytes,
+        ) {
+            if let Err(error) = mastodon::post_status(
+                mastodon_url,
+                mastodon_token,
+                image_bytes,
+                &caption.to_plain_text(&catalog),
+            )
+            .await
+            {
+                warn!(
+                    id = %illust.id,
+                    error = ?error,
+                    "Failed cross-posting artwork to Mastodon"
+                );
+            }
+        }
+
+        // remember this illustration so it is not reposted within the TTL
+        cache.set(&illust.id, Utc::now().timestamp()).await?;
         Ok(())
     }
 }
@@ -540,6 +928,25 @@ pub async fn run(config: Config) -> Result<()> {
     // initialize bot instance
     let bot = Bot::new(&config.token).throttle(Limits::default());
 
+    // build the Pixiv client once, routed through the configured proxy
+    // if any, and reuse it for every request instead of rebuilding
+    let pixiv_client = build_pixiv_client(&config)?;
+
+    // build the Telegraph client once and reuse it for every request,
+    // the same way `pixiv_client` is built once and threaded through
+    let telegraph_client = reqwest::Client::builder().user_agent(USER_AGENT).build()?;
+
+    // open the dedup cache that keeps already-posted illustrations from
+    // being reposted while they are still ranked
+    let cache: Arc<dyn KvStore> = Arc::new(FileKvStore::open(&config.cache_path)?);
+
+    // load the caption label catalog for the configured locale
+    let catalog = Arc::new(Catalog::load(&config.lang)?);
+
+    // bounds how many detail-fetch/send tasks run at once, so a large
+    // --pages value doesn't fire unbounded concurrent requests
+    let semaphore = Arc::new(Semaphore::new(config.concurrency));
+
     // fetch daily top 50
     let today = Utc::now().format("%B %-d, %Y").to_string();
     info!(
@@ -551,10 +958,13 @@ pub async fn run(config: Config) -> Result<()> {
 
     // push get illust detail tasks into a Vec
     let mut get_illust_tasks: Vec<JoinHandle<Result<Illust, reqwest::Error>>> = vec![];
-    for illust in get_pixiv_daily_ranking(&config).await? {
-        get_illust_tasks.push(task::spawn(get_illust_details(
-            illust.illust_id.to_string(),
-        )));
+    for illust in get_pixiv_daily_ranking(&config, &pixiv_client).await? {
+        let semaphore = semaphore.clone();
+        let pixiv_client = pixiv_client.clone();
+        get_illust_tasks.push(task::spawn(async move {
+            let _permit = semaphore.acquire_owned().await.expect("semaphore closed");
+            get_illust_details(&pixiv_client, illust.illust_id.to_string()).await
+        }));
     }
 
     // send today's date and pin the message
@@ -563,14 +973,29 @@ pub async fn run(config: Config) -> Result<()> {
         .disable_notification(true)
         .await?;
 
-    // send each of the illustrations
+    // send each of the illustrations, skipping ones already posted recently
     let mut send_illust_tasks: Vec<JoinHandle<Result<(), anyhow::Error>>> = vec![];
     for illust in future::join_all(get_illust_tasks).await {
-        send_illust_tasks.push(task::spawn(send_illust(
-            config.clone(),
-            bot.clone(),
-            illust??,
-        )));
+        let illust = illust??;
+
+        if let Some(last_sent) = cache.get(&illust.id).await? {
+            if Utc::now().timestamp() - last_sent < config.cache_ttl {
+                debug!(id = %illust.id, "Skipping recently-posted illustration");
+                continue;
+            }
+        }
+
+        let semaphore = semaphore.clone();
+        let config = config.clone();
+        let bot = bot.clone();
+        let cache = cache.clone();
+        let pixiv_client = pixiv_client.clone();
+        let telegraph_client = telegraph_client.clone();
+        let catalog = catalog.clone();
+        send_illust_tasks.push(task::spawn(async move {
+            let _permit = semaphore.acquire_owned().await.expect("semaphore closed");
+            send_illust(config, bot, cache, pixiv_client, telegraph_client, catalog, illust).await
+        }));
     }
 
     // print errors in finished tasks if any
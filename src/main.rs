@@ -1,9 +1,8 @@
-use std::{process, sync::Mutex};
+use std::{path::PathBuf, process};
 
 use anyhow::Result;
 use clap::{value_t_or_exit, Arg};
 use pixivdaily::{run, Config, VERSION};
-use slog::{o, Drain};
 
 /// parse the command line arguments and return a new
 /// Config instance
@@ -41,18 +40,152 @@ fn parse() -> Result<Config> {
                 .takes_value(true)
                 .env("TELOXIDE_TOKEN"),
         )
+        .arg(
+            Arg::with_name("pages")
+                .short("p")
+                .long("pages")
+                .value_name("PAGES")
+                .help("number of Pixiv daily ranking pages to fetch")
+                .takes_value(true)
+                .default_value("1")
+                .env("PIXIV_PAGES"),
+        )
+        .arg(
+            Arg::with_name("r18")
+                .long("r18")
+                .help("fetch the R18 ranking instead of the regular one")
+                .takes_value(false)
+                .env("PIXIV_R18"),
+        )
+        .arg(
+            Arg::with_name("telegraph-token")
+                .long("telegraph-token")
+                .value_name("TELEGRAPHTOKEN")
+                .help("Telegraph access token used to publish manga with more than 10 pages")
+                .takes_value(true)
+                .env("TELEGRAPH_TOKEN"),
+        )
+        .arg(
+            Arg::with_name("cache-path")
+                .long("cache-path")
+                .value_name("CACHEPATH")
+                .help("path to the dedup cache's backing file")
+                .takes_value(true)
+                .default_value("pixivdaily_cache.json")
+                .env("CACHE_PATH"),
+        )
+        .arg(
+            Arg::with_name("cache-ttl")
+                .long("cache-ttl")
+                .value_name("CACHETTL")
+                .help("seconds a posted illustration is remembered before it can be reposted")
+                .takes_value(true)
+                .env("CACHE_TTL"),
+        )
+        .arg(
+            Arg::with_name("concurrency")
+                .long("concurrency")
+                .value_name("CONCURRENCY")
+                .help("maximum number of detail-fetch/send tasks running at once")
+                .takes_value(true)
+                .env("CONCURRENCY"),
+        )
+        .arg(
+            Arg::with_name("batch-max-items")
+                .long("batch-max-items")
+                .value_name("BATCHMAXITEMS")
+                .help("maximum number of pages per Telegram media group (capped at 10)")
+                .takes_value(true)
+                .env("BATCH_MAX_ITEMS"),
+        )
+        .arg(
+            Arg::with_name("batch-max-bytes")
+                .long("batch-max-bytes")
+                .value_name("BATCHMAXBYTES")
+                .help("maximum accumulated byte size of a single Telegram media group")
+                .takes_value(true)
+                .env("BATCH_MAX_BYTES"),
+        )
+        .arg(
+            Arg::with_name("proxy")
+                .long("proxy")
+                .value_name("PROXY")
+                .help("SOCKS/HTTP proxy URL to route Pixiv requests through")
+                .takes_value(true)
+                .env("PIXIV_PROXY"),
+        )
+        .arg(
+            Arg::with_name("mastodon-url")
+                .long("mastodon-url")
+                .value_name("MASTODONURL")
+                .help("base URL of the Mastodon instance to cross-post to")
+                .takes_value(true)
+                .env("MASTODON_URL"),
+        )
+        .arg(
+            Arg::with_name("mastodon-token")
+                .long("mastodon-token")
+                .value_name("MASTODONTOKEN")
+                .help("Mastodon account access token")
+                .takes_value(true)
+                .env("MASTODON_TOKEN"),
+        )
+        .arg(
+            Arg::with_name("imgur-client-id")
+                .long("imgur-client-id")
+                .value_name("IMGURCLIENTID")
+                .help("Imgur client ID used to host oversized artwork instead of downscaling it")
+                .takes_value(true)
+                .env("IMGUR_CLIENT_ID"),
+        )
+        .arg(
+            Arg::with_name("lang")
+                .long("lang")
+                .value_name("LANG")
+                .help("locale captions are rendered in (en-US, ja, zh); falls back to en-US")
+                .takes_value(true)
+                .default_value("en-US")
+                .env("PIXIVDAILY_LANG"),
+        )
         .get_matches();
 
     // assign command line values to variables
-    Ok(Config::new(
-        {
-            let decorator = slog_term::TermDecorator::new().build();
-            let drain = Mutex::new(slog_term::FullFormat::new(decorator).build()).fuse();
-            slog::Logger::root(drain, o!())
-        },
+    let mut config = Config::new(
         value_t_or_exit!(matches.value_of("token"), String),
         value_t_or_exit!(matches.value_of("chat-id"), i64),
-    ))
+    )
+    .with_pages(value_t_or_exit!(matches.value_of("pages"), u32))
+    .with_r18(matches.is_present("r18"))
+    .with_telegraph_token(matches.value_of("telegraph-token").map(String::from))
+    .with_cache_path(PathBuf::from(
+        matches.value_of("cache-path").unwrap_or("pixivdaily_cache.json"),
+    ));
+
+    if let Some(cache_ttl) = matches.value_of("cache-ttl") {
+        config = config.with_cache_ttl(cache_ttl.parse()?);
+    }
+
+    if let Some(concurrency) = matches.value_of("concurrency") {
+        config = config.with_concurrency(concurrency.parse()?);
+    }
+
+    if let Some(batch_max_items) = matches.value_of("batch-max-items") {
+        config = config.with_batch_max_items(batch_max_items.parse()?);
+    }
+
+    if let Some(batch_max_bytes) = matches.value_of("batch-max-bytes") {
+        config = config.with_batch_max_bytes(batch_max_bytes.parse()?);
+    }
+
+    config = config.with_proxy(matches.value_of("proxy").map(String::from));
+    config = config.with_mastodon(
+        matches.value_of("mastodon-url").map(String::from),
+        matches.value_of("mastodon-token").map(String::from),
+    );
+    config = config.with_imgur_client_id(matches.value_of("imgur-client-id").map(String::from));
+    config = config.with_lang(value_t_or_exit!(matches.value_of("lang"), String));
+
+    Ok(config)
 }
 
 /// program entry point
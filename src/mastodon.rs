@@ -0,0 +1,72 @@
+/*
+ * Copyright (C) 2021-2025 K4YT3X.
+ *
+ * This program is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU General Public License
+ * as published by the Free Software Foundation; only version 2
+ * of the License.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+//! optional Mastodon cross-posting backend, built on `megalodon`
+
+use std::io::Write;
+
+use anyhow::{anyhow, Result};
+use megalodon::{generator, megalodon::PostStatusInputOptions, SNS};
+use tempfile::NamedTempFile;
+
+/// upload an image and publish a status linking to the Pixiv artwork
+///
+/// # Arguments
+///
+/// * `instance_url` - base URL of the Mastodon instance
+/// * `access_token` - account access token
+/// * `image_bytes` - the (already resized) image to attach
+/// * `text` - plain-text status body
+///
+/// # Errors
+///
+/// any error returned by the Mastodon instance, or I/O errors while
+/// staging the image for upload
+///
+/// # Examples
+///
+/// ```
+/// post_status(&url, &token, &image_bytes, &text).await?;
+/// ```
+pub async fn post_status(
+    instance_url: &String,
+    access_token: &String,
+    image_bytes: &[u8],
+    text: &String,
+) -> Result<()> {
+    let client = generator(SNS::Mastodon, instance_url.clone(), Some(access_token.clone()), None)?;
+
+    // megalodon uploads media from a path on disk; stage the bytes there
+    let mut staged_image = NamedTempFile::new()?;
+    staged_image.write_all(image_bytes)?;
+
+    let media = client
+        .upload_media(staged_image.path().to_string_lossy().to_string(), None)
+        .await
+        .map_err(|error| anyhow!("Mastodon media upload failed: {error}"))?;
+
+    let options = PostStatusInputOptions {
+        media_ids: Some(vec![media.json.id]),
+        ..Default::default()
+    };
+
+    client
+        .post_status(text.to_owned(), Some(&options))
+        .await
+        .map_err(|error| anyhow!("Mastodon post_status failed: {error}"))?;
+
+    Ok(())
+}
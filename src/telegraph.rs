@@ -0,0 +1,175 @@
+/*
+ * Copyright (C) 2021-2025 K4YT3X.
+ *
+ * This program is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU General Public License
+ * as published by the Free Software Foundation; only version 2
+ * of the License.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+//! minimal client for the subset of Telegraph's API used to host
+//! manga pages that do not fit into a single Telegram media group
+
+use anyhow::{anyhow, Result};
+use reqwest::multipart;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+const UPLOAD_ENDPOINT: &'static str = "https://telegra.ph/upload";
+const CREATE_PAGE_ENDPOINT: &'static str = "https://api.telegra.ph/createPage";
+
+/// a single node in a Telegraph page's `content` tree
+///
+/// Telegraph pages are built from a JSON array of these nodes; a node is
+/// either a bare string or a tagged element that may itself contain nodes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Node {
+    Text(String),
+    Element(NodeElement),
+}
+
+/// a tagged Telegraph node, e.g. `{"tag":"img","attrs":{"src":"..."}}`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeElement {
+    pub tag: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attrs: Option<NodeAttrs>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub children: Option<Vec<Node>>,
+}
+
+/// attributes attached to a [`NodeElement`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeAttrs {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub src: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub href: Option<String>,
+}
+
+impl NodeElement {
+    /// build an `<img src="...">` node pointing at a Telegraph-hosted file
+    pub fn img(src: String) -> Node {
+        Node::Element(NodeElement {
+            tag: "img".to_owned(),
+            attrs: Some(NodeAttrs {
+                src: Some(src),
+                href: None,
+            }),
+            children: None,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct UploadResponseItem {
+    src: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct CreatePageResponse {
+    ok: bool,
+    error: Option<String>,
+    result: Option<CreatePageResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CreatePageResult {
+    url: String,
+}
+
+/// upload a single image to Telegraph's media endpoint
+///
+/// # Arguments
+///
+/// * `client` - the `reqwest::Client` to issue the request with
+/// * `image_bytes` - raw bytes of the (already resized) image
+///
+/// # Errors
+///
+/// reqwest errors, or an error if Telegraph returns an empty result
+///
+/// # Examples
+///
+/// ```
+/// let src = upload_media(&client, image_bytes).await?;
+/// ```
+pub async fn upload_media(client: &reqwest::Client, image_bytes: Vec<u8>) -> Result<String> {
+    let part = multipart::Part::bytes(image_bytes).file_name("image.png");
+    let form = multipart::Form::new().part("file", part);
+
+    let items = client
+        .post(UPLOAD_ENDPOINT)
+        .multipart(form)
+        .send()
+        .await?
+        .json::<Vec<UploadResponseItem>>()
+        .await?;
+
+    items
+        .into_iter()
+        .next()
+        .map(|item| format!("https://telegra.ph{}", item.src))
+        .ok_or_else(|| anyhow!("Telegraph upload returned no file"))
+}
+
+/// create a Telegraph page from a list of content nodes
+///
+/// # Arguments
+///
+/// * `client` - the `reqwest::Client` to issue the request with
+/// * `access_token` - Telegraph account access token
+/// * `title` - page title
+/// * `author_name` - page author byline
+/// * `content` - the page body as a list of [`Node`]s
+///
+/// # Errors
+///
+/// reqwest errors, or an error if Telegraph rejects the request
+///
+/// # Examples
+///
+/// ```
+/// let url = create_page(&client, &token, &title, &author, nodes).await?;
+/// ```
+pub async fn create_page(
+    client: &reqwest::Client,
+    access_token: &String,
+    title: &String,
+    author_name: &String,
+    content: Vec<Node>,
+) -> Result<String> {
+    let response = client
+        .post(CREATE_PAGE_ENDPOINT)
+        .form(&[
+            ("access_token", access_token.as_str()),
+            ("title", title.as_str()),
+            ("author_name", author_name.as_str()),
+            ("content", &serde_json::to_string(&content)?),
+            ("return_content", &json!(false).to_string()),
+        ])
+        .send()
+        .await?
+        .json::<CreatePageResponse>()
+        .await?;
+
+    if !response.ok {
+        return Err(anyhow!(
+            "Telegraph createPage failed: {}",
+            response.error.unwrap_or_else(|| "unknown error".to_owned())
+        ));
+    }
+
+    response
+        .result
+        .map(|result| result.url)
+        .ok_or_else(|| anyhow!("Telegraph createPage returned no result"))
+}